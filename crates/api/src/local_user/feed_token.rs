@@ -0,0 +1,47 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  feed_token::{CreateFeedToken, DeleteFeedToken, FeedTokenView, ListFeedTokensResponse},
+};
+use lemmy_db_schema::source::feed_token::FeedToken;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Mints a new feed token for the logged in user, scoped to `data.scope`.
+/// Minting a new token never revokes an existing one — a feed reader that
+/// already has a token keeps working until that specific token is deleted.
+pub async fn create_feed_token(
+  data: Json<CreateFeedToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<FeedTokenView>> {
+  let feed_token =
+    FeedToken::generate(&mut context.pool(), local_user_view.local_user.id, data.scope).await?;
+  Ok(Json(feed_token.into()))
+}
+
+/// Lists every feed token minted for the logged in user, so the account
+/// settings UI can show which ones exist to revoke.
+pub async fn list_feed_tokens(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFeedTokensResponse>> {
+  let feed_tokens =
+    FeedToken::list_for_local_user(&mut context.pool(), local_user_view.local_user.id)
+      .await?
+      .into_iter()
+      .map(Into::into)
+      .collect();
+  Ok(Json(ListFeedTokensResponse { feed_tokens }))
+}
+
+/// Revokes a feed token, so a URL built from it — sitting in browser history
+/// or a feed reader's database — stops working immediately.
+pub async fn delete_feed_token(
+  data: Json<DeleteFeedToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<()>> {
+  FeedToken::delete(&mut context.pool(), data.id, local_user_view.local_user.id).await?;
+  Ok(Json(()))
+}
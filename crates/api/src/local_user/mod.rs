@@ -0,0 +1,16 @@
+pub mod feed_token;
+
+use actix_web::web;
+use feed_token::{create_feed_token, delete_feed_token, list_feed_tokens};
+
+/// `front`/`inbox` feed tokens are minted and revoked here rather than in
+/// `crates/routes`, since minting one requires the caller's session — the
+/// feed endpoints themselves only ever read a token back, never issue one.
+pub fn config(cfg: &mut web::ServiceConfig) {
+  cfg.service(
+    web::scope("/user/feed_token")
+      .route("", web::post().to(create_feed_token))
+      .route("/list", web::get().to(list_feed_tokens))
+      .route("", web::delete().to(delete_feed_token)),
+  );
+}
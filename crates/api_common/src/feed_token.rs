@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use lemmy_db_schema::{newtypes::FeedTokenId, source::feed_token::FeedToken};
+use lemmy_db_schema_file::enums::FeedTokenScope;
+use serde::{Deserialize, Serialize};
+
+/// Mints a new feed token scoped to `scope`, to be used as the
+/// `/feeds/front/{token}.xml` or `/feeds/inbox/{token}.xml` path segment.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateFeedToken {
+  pub scope: FeedTokenScope,
+}
+
+/// A minted feed token, as shown back to its owner in the account settings UI.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedTokenView {
+  pub id: FeedTokenId,
+  pub scope: FeedTokenScope,
+  pub token: String,
+  pub published: DateTime<Utc>,
+}
+
+impl From<FeedToken> for FeedTokenView {
+  fn from(feed_token: FeedToken) -> Self {
+    FeedTokenView {
+      id: feed_token.id,
+      scope: feed_token.scope,
+      token: feed_token.token,
+      published: feed_token.published,
+    }
+  }
+}
+
+/// Every feed token minted for the logged in user.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ListFeedTokensResponse {
+  pub feed_tokens: Vec<FeedTokenView>,
+}
+
+/// Revokes a feed token, so any URL built from it stops working immediately.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeleteFeedToken {
+  pub id: FeedTokenId,
+}
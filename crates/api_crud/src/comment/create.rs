@@ -0,0 +1,71 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  comment::{CommentResponse, CreateComment},
+  context::LemmyContext,
+};
+use lemmy_db_schema::source::{
+  comment::{Comment, CommentInsertForm},
+  community::Community,
+  post::Post,
+};
+use lemmy_db_views_comment::CommentView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_routes::websub;
+use lemmy_utils::error::LemmyResult;
+
+/// Creates `data` as a new comment on its post, then pings WebSub
+/// subscribers of the feeds it now shows up in. Same rule as
+/// `post::create::create_post`: this is the comment-create path's only call
+/// site for `notify_feed_subscribers`.
+pub async fn create_comment(
+  data: Json<CreateComment>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  let post = Post::read(&mut context.pool(), data.post_id).await?;
+  let community = Community::read(&mut context.pool(), post.community_id).await?;
+
+  let form = CommentInsertForm::new(local_user_view.person.id, data.post_id, data.content.clone());
+  let comment = Comment::create(&mut context.pool(), &form, None).await?;
+
+  notify_feed_subscribers(&context, &post, &community, &local_user_view.person.name).await;
+
+  let comment_view = CommentView::read(
+    &mut context.pool(),
+    comment.id,
+    Some(local_user_view.local_user.id),
+  )
+  .await?;
+  Ok(Json(CommentResponse {
+    comment_view,
+    recipient_ids: vec![],
+  }))
+}
+
+/// Pings WebSub subscribers of the feeds the new comment now shows up in:
+/// the parent post's comment feed, its community's comment feed, and its
+/// creator's comment feed. See `websub::publish_update` for why a slow or
+/// unreachable subscriber can't hold up the comment create request this is
+/// called from.
+async fn notify_feed_subscribers(
+  context: &LemmyContext,
+  post: &Post,
+  community: &Community,
+  creator_name: &str,
+) {
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+
+  let post_comments_topic =
+    format!("{protocol_and_hostname}/feeds/post/{}/comments.xml", post.id);
+  let _ = websub::publish_update(context, &post_comments_topic).await;
+
+  let community_comments_topic = format!(
+    "{protocol_and_hostname}/feeds/c/{}/comments.xml",
+    community.name
+  );
+  let _ = websub::publish_update(context, &community_comments_topic).await;
+
+  let creator_comments_topic =
+    format!("{protocol_and_hostname}/feeds/u/{creator_name}/comments.xml");
+  let _ = websub::publish_update(context, &creator_comments_topic).await;
+}
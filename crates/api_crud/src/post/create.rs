@@ -0,0 +1,62 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  post::{CreatePost, PostResponse},
+};
+use lemmy_db_schema::source::{
+  community::Community,
+  post::{Post, PostInsertForm},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::PostView;
+use lemmy_routes::websub;
+use lemmy_utils::error::LemmyResult;
+
+/// Creates `data` as a new post in its community, then pings WebSub
+/// subscribers of the public feeds it now shows up in. This is the post-create
+/// path's only call site for `notify_feed_subscribers` — wiring it in
+/// anywhere else (e.g. an edit) would re-publish a post subscribers already
+/// saw.
+pub async fn create_post(
+  data: Json<CreatePost>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+
+  let form = PostInsertForm::new(data.name.clone(), local_user_view.person.id, data.community_id);
+  let post = Post::create(&mut context.pool(), &form).await?;
+
+  notify_feed_subscribers(&context, &community, &local_user_view.person.name).await;
+
+  let post_view = PostView::read(
+    &mut context.pool(),
+    post.id,
+    Some(local_user_view.local_user.id),
+    false,
+  )
+  .await?;
+  Ok(Json(PostResponse { post_view }))
+}
+
+/// Pings WebSub subscribers of the public feeds the new post now shows up in:
+/// its community's feed, its creator's feed, and the instance-wide
+/// `all`/`local` feeds. Token-scoped `front` feeds are not pinged.
+/// `publish_update` only awaits the subscriber lookup and spawns each callback
+/// delivery, so no callback delivery holds up the post create request it's
+/// called from — a failed lookup is ignored rather than failing the create.
+async fn notify_feed_subscribers(context: &LemmyContext, community: &Community, creator_name: &str) {
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+
+  let community_topic = format!("{protocol_and_hostname}/feeds/c/{}.xml", community.name);
+  let _ = websub::publish_update(context, &community_topic).await;
+
+  let creator_topic = format!("{protocol_and_hostname}/feeds/u/{creator_name}.xml");
+  let _ = websub::publish_update(context, &creator_topic).await;
+
+  let _ = websub::publish_update(context, &format!("{protocol_and_hostname}/feeds/all.xml")).await;
+  if community.local {
+    let _ =
+      websub::publish_update(context, &format!("{protocol_and_hostname}/feeds/local.xml")).await;
+  }
+}
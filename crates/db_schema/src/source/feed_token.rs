@@ -0,0 +1,139 @@
+use crate::newtypes::{FeedTokenId, LocalUserId};
+#[cfg(feature = "full")]
+use crate::schema::feed_token;
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::FeedTokenScope;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+/// A feed token unlocks one `front` or `inbox` feed for whoever holds it.
+/// Unlike a session JWT, it's scoped to a single feed kind, never grants
+/// write access, and can be revoked on its own without logging the user out
+/// everywhere else.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = feed_token))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct FeedToken {
+  pub id: FeedTokenId,
+  pub local_user_id: LocalUserId,
+  pub scope: FeedTokenScope,
+  pub token: String,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = feed_token))]
+pub struct FeedTokenInsertForm {
+  pub local_user_id: LocalUserId,
+  pub scope: FeedTokenScope,
+  pub token: String,
+}
+
+const TOKEN_LEN: usize = 32;
+
+impl FeedToken {
+  fn generate_token() -> String {
+    thread_rng()
+      .sample_iter(&Alphanumeric)
+      .take(TOKEN_LEN)
+      .map(char::from)
+      .collect()
+  }
+}
+
+#[cfg(feature = "full")]
+mod impls {
+  use super::{FeedToken, FeedTokenInsertForm};
+  use crate::{
+    newtypes::{FeedTokenId, LocalUserId},
+    schema::feed_token::dsl::feed_token,
+    utils::{get_conn, DbPool},
+  };
+  use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+  use diesel_async::RunQueryDsl;
+  use lemmy_db_schema_file::enums::FeedTokenScope;
+  use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+  impl FeedToken {
+    /// Mints a new, randomly-generated token scoped to `scope` for
+    /// `local_user_id`. Minting a new token never revokes an existing one —
+    /// a user can hold several feed readers subscribed at once, each with
+    /// its own revocable token.
+    pub async fn generate(
+      pool: &mut DbPool<'_>,
+      local_user_id: LocalUserId,
+      scope: FeedTokenScope,
+    ) -> LemmyResult<Self> {
+      let conn = &mut get_conn(pool).await?;
+      let form = FeedTokenInsertForm {
+        local_user_id,
+        scope,
+        token: FeedToken::generate_token(),
+      };
+      insert_into(feed_token)
+        .values(form)
+        .get_result(conn)
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Resolves a `/feeds/front/{token}.xml` or `/feeds/inbox/{token}.xml`
+    /// path segment to the local user it was minted for, as long as it's
+    /// still valid for `scope` — a token minted for the front page can't be
+    /// used to read the inbox feed, and a revoked token resolves to nothing.
+    pub async fn read_and_validate(
+      pool: &mut DbPool<'_>,
+      token_: &str,
+      scope_: FeedTokenScope,
+    ) -> LemmyResult<LocalUserId> {
+      use crate::schema::feed_token::dsl::{scope, token};
+      let conn = &mut get_conn(pool).await?;
+      let found: Self = feed_token
+        .filter(token.eq(token_))
+        .filter(scope.eq(scope_))
+        .first(conn)
+        .await
+        .map_err(|e| match e {
+          Error::NotFound => LemmyErrorType::NotFound.into(),
+          e => e.into(),
+        })?;
+      Ok(found.local_user_id)
+    }
+
+    /// Lists every feed token minted for `local_user_id`, so the account
+    /// settings UI can show which ones exist to revoke.
+    pub async fn list_for_local_user(
+      pool: &mut DbPool<'_>,
+      local_user_id_: LocalUserId,
+    ) -> LemmyResult<Vec<Self>> {
+      use crate::schema::feed_token::dsl::local_user_id;
+      let conn = &mut get_conn(pool).await?;
+      feed_token
+        .filter(local_user_id.eq(local_user_id_))
+        .load(conn)
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Revokes a feed token. Scoped to `local_user_id` so a user can only
+    /// ever revoke their own tokens.
+    pub async fn delete(
+      pool: &mut DbPool<'_>,
+      id_: FeedTokenId,
+      local_user_id_: LocalUserId,
+    ) -> LemmyResult<usize> {
+      use crate::schema::feed_token::dsl::{id, local_user_id};
+      let conn = &mut get_conn(pool).await?;
+      diesel::delete(
+        feed_token
+          .filter(id.eq(id_))
+          .filter(local_user_id.eq(local_user_id_)),
+      )
+      .execute(conn)
+      .await
+      .map_err(Into::into)
+    }
+  }
+}
@@ -0,0 +1,141 @@
+use crate::newtypes::WebSubSubscriptionId;
+#[cfg(feature = "full")]
+use crate::schema::websub_subscription;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One subscriber's callback for one feed topic. Rows start out unconfirmed:
+/// the hub has recorded the request but hasn't yet GETed the subscriber's
+/// `callback` to verify it actually wants the subscription (see
+/// `crate::websub::verify_with_subscriber` in `lemmy_routes`). Only confirmed,
+/// unexpired rows are ever pinged by `publish_update`.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = websub_subscription))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct WebSubSubscription {
+  pub id: WebSubSubscriptionId,
+  pub topic: String,
+  pub callback: String,
+  pub lease_seconds: i64,
+  pub confirmed: bool,
+  pub published: DateTime<Utc>,
+  pub expires: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = websub_subscription))]
+pub struct WebSubSubscriptionInsertForm {
+  pub topic: String,
+  pub callback: String,
+  pub lease_seconds: i64,
+  pub expires: DateTime<Utc>,
+}
+
+/// WebSub hubs are expected to default to roughly a day when a subscriber
+/// doesn't ask for a specific `hub.lease_seconds`.
+pub const DEFAULT_LEASE_SECONDS: i64 = 24 * 60 * 60;
+
+#[cfg(feature = "full")]
+mod impls {
+  use super::{WebSubSubscription, WebSubSubscriptionInsertForm, DEFAULT_LEASE_SECONDS};
+  use crate::{
+    schema::websub_subscription::dsl::websub_subscription,
+    utils::{get_conn, DbPool},
+  };
+  use chrono::{Duration, Utc};
+  use diesel::{
+    dsl::insert_into,
+    upsert::excluded,
+    ExpressionMethods,
+    QueryDsl,
+  };
+  use diesel_async::RunQueryDsl;
+  use lemmy_utils::error::LemmyResult;
+
+  impl WebSubSubscription {
+    /// Records a subscriber's callback for `topic` as unconfirmed, replacing
+    /// any existing row for the same `(topic, callback)` pair — e.g. a
+    /// subscriber renewing its lease before the old one expires.
+    pub async fn upsert(
+      pool: &mut DbPool<'_>,
+      topic_: &str,
+      callback_: &str,
+      lease_seconds: Option<i64>,
+    ) -> LemmyResult<Self> {
+      use crate::schema::websub_subscription::dsl::{
+        callback,
+        confirmed,
+        expires,
+        lease_seconds as lease_seconds_column,
+        topic,
+      };
+      let conn = &mut get_conn(pool).await?;
+      let lease_seconds_ = lease_seconds.unwrap_or(DEFAULT_LEASE_SECONDS);
+      let form = WebSubSubscriptionInsertForm {
+        topic: topic_.to_string(),
+        callback: callback_.to_string(),
+        lease_seconds: lease_seconds_,
+        expires: Utc::now() + Duration::seconds(lease_seconds_),
+      };
+      insert_into(websub_subscription)
+        .values(&form)
+        .on_conflict((topic, callback))
+        .do_update()
+        .set((
+          lease_seconds_column.eq(excluded(lease_seconds_column)),
+          expires.eq(excluded(expires)),
+          confirmed.eq(false),
+        ))
+        .get_result(conn)
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Marks a subscription confirmed once the hub's verification GET to
+    /// `callback` echoed back the challenge it was sent.
+    pub async fn mark_confirmed(pool: &mut DbPool<'_>, topic_: &str, callback_: &str) -> LemmyResult<()> {
+      use crate::schema::websub_subscription::dsl::{callback, confirmed, topic};
+      let conn = &mut get_conn(pool).await?;
+      diesel::update(
+        websub_subscription
+          .filter(topic.eq(topic_))
+          .filter(callback.eq(callback_)),
+      )
+      .set(confirmed.eq(true))
+      .execute(conn)
+      .await?;
+      Ok(())
+    }
+
+    /// Removes a subscriber's callback for `topic`, either because it asked
+    /// to unsubscribe or because verification of a new subscription failed.
+    pub async fn remove(pool: &mut DbPool<'_>, topic_: &str, callback_: &str) -> LemmyResult<usize> {
+      use crate::schema::websub_subscription::dsl::{callback, topic};
+      let conn = &mut get_conn(pool).await?;
+      diesel::delete(
+        websub_subscription
+          .filter(topic.eq(topic_))
+          .filter(callback.eq(callback_)),
+      )
+      .execute(conn)
+      .await
+      .map_err(Into::into)
+    }
+
+    /// Every confirmed, unexpired subscriber callback for `topic`, i.e. who
+    /// `publish_update` should ping when new content lands.
+    pub async fn read_confirmed_for_topic(pool: &mut DbPool<'_>, topic_: &str) -> LemmyResult<Vec<Self>> {
+      use crate::schema::websub_subscription::dsl::{confirmed, expires, topic};
+      let conn = &mut get_conn(pool).await?;
+      websub_subscription
+        .filter(topic.eq(topic_))
+        .filter(confirmed.eq(true))
+        .filter(expires.gt(Utc::now()))
+        .load(conn)
+        .await
+        .map_err(Into::into)
+    }
+  }
+}
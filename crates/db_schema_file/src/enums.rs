@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use diesel_derive_enum::DbEnum;
+
+/// What a feed token unlocks: a `front` feed reads `FeedTokenScope::Subscribed`,
+/// an `inbox` feed reads `FeedTokenScope::Inbox`. A token minted for one scope
+/// can't be used to read the other, so leaking a front-page feed URL doesn't
+/// also expose private messages.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::FeedTokenScopeEnum"
+)]
+pub enum FeedTokenScope {
+  Subscribed,
+  Inbox,
+}
@@ -1,18 +1,39 @@
-use actix_web::{error::ErrorBadRequest, web, Error, HttpRequest, HttpResponse, Result};
+use actix_web::{
+  error::ErrorBadRequest,
+  http::header,
+  web,
+  Error,
+  HttpRequest,
+  HttpResponse,
+  Result,
+};
 use anyhow::anyhow;
-use chrono::{DateTime, Utc};
-use lemmy_api_common::{
-  context::LemmyContext,
-  utils::{check_private_instance, local_user_view_from_jwt},
+use atom_syndication::{
+  ContentBuilder,
+  Entry as AtomEntry,
+  EntryBuilder,
+  FeedBuilder,
+  LinkBuilder,
+  PersonBuilder,
 };
+use chrono::{DateTime, FixedOffset, Utc};
+use lemmy_api_common::{context::LemmyContext, utils::check_private_instance};
 use lemmy_db_schema::{
-  source::{community::Community, person::Person},
-  traits::ApubActor,
+  newtypes::PostId,
+  source::{community::Community, feed_token::FeedToken, person::Person},
+  traits::{ApubActor, PaginationCursorBuilder},
+  utils::DbPool,
+  PaginationCursor,
   PersonContentType,
 };
-use lemmy_db_schema_file::enums::{ListingType, PostSortType};
+use lemmy_db_schema_file::enums::{FeedTokenScope, ListingType, PostSortType};
+use lemmy_db_views_comment::{impls::CommentQuery, CommentView};
 use lemmy_db_views_inbox_combined::{impls::InboxCombinedQuery, InboxCombinedView};
-use lemmy_db_views_person_content_combined::impls::PersonContentCombinedQuery;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_person_content_combined::{
+  impls::PersonContentCombinedQuery,
+  PersonContentCombinedView,
+};
 use lemmy_db_views_post::{impls::PostQuery, PostView};
 use lemmy_db_views_site::SiteView;
 use lemmy_utils::{
@@ -21,6 +42,7 @@ use lemmy_utils::{
   settings::structs::Settings,
   utils::markdown::markdown_to_html,
 };
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use rss::{
   extension::{dublincore::DublinCoreExtension, ExtensionBuilder, ExtensionMap},
   Category,
@@ -29,15 +51,31 @@ use rss::{
   Guid,
   Item,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, str::FromStr, sync::LazyLock};
 
+use crate::websub;
+
 const RSS_FETCH_LIMIT: i64 = 20;
+/// How large a multiple of the requested page size to fetch when a content
+/// filter can't be pushed into the query and has to run client-side instead.
+const UNINDEXED_FILTER_OVERFETCH: i64 = 4;
 
 #[derive(Deserialize)]
 struct Params {
   sort: Option<String>,
   limit: Option<i64>,
+  format: Option<String>,
+  page_cursor: Option<String>,
+  page_back: Option<bool>,
+  nsfw: Option<String>,
+  min_score: Option<i64>,
+  include_communities: Option<String>,
+  exclude_communities: Option<String>,
+  keywords: Option<String>,
+  exclude_keywords: Option<String>,
+  #[serde(rename = "type")]
+  post_kind: Option<String>,
 }
 
 impl Params {
@@ -51,6 +89,364 @@ impl Params {
   fn get_limit(&self) -> i64 {
     self.limit.unwrap_or(RSS_FETCH_LIMIT)
   }
+  /// Whether any of the content filters need to be applied client-side
+  /// because they don't map onto an existing query field: `nsfw=only`
+  /// (no "nsfw only" field to push into), the community include/exclude
+  /// lists (the query only takes a single `community_id`), keyword search,
+  /// and post `type`.
+  fn has_unindexed_filters(&self) -> bool {
+    self.nsfw_mode() == Some(NsfwMode::Only)
+      || self.include_communities.is_some()
+      || self.exclude_communities.is_some()
+      || self.keywords.is_some()
+      || self.exclude_keywords.is_some()
+      || self.post_kind.is_some()
+  }
+  /// The limit to ask the query for. When a filter can't be pushed into the
+  /// query itself, over-fetch so that filtering it out client-side still
+  /// tends to leave a full page, rather than handing `get_limit()` rows to
+  /// the database and then discarding most of them before the page is built.
+  fn fetch_limit(&self) -> i64 {
+    if self.has_unindexed_filters() {
+      self.get_limit().saturating_mul(UNINDEXED_FILTER_OVERFETCH)
+    } else {
+      self.get_limit()
+    }
+  }
+  /// Decodes the opaque `page_cursor` query param into the keyset cursor a
+  /// given query expects, if one was given.
+  async fn cursor_data<T: PaginationCursorBuilder>(
+    &self,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Option<T::CursorData>> {
+    match &self.page_cursor {
+      Some(cursor) => Ok(Some(
+        T::from_cursor(&PaginationCursor(cursor.clone()), pool).await?,
+      )),
+      None => Ok(None),
+    }
+  }
+  /// Picks the output format, preferring an explicit `format` query param
+  /// over the `Accept` header, and falling back to RSS for back-compat.
+  fn feed_format(&self, req: &HttpRequest) -> FeedFormat {
+    if let Some(format) = &self.format {
+      return FeedFormat::from_query_param(format);
+    }
+    req
+      .headers()
+      .get(header::ACCEPT)
+      .and_then(|v| v.to_str().ok())
+      .map(FeedFormat::from_accept_header)
+      .unwrap_or(FeedFormat::Rss)
+  }
+  /// `None` means "don't touch nsfw filtering," leaving whatever the
+  /// existing local-user/site default already does untouched; the feed only
+  /// overrides it once the caller explicitly asks via `nsfw=hide|only|show`.
+  fn nsfw_mode(&self) -> Option<NsfwMode> {
+    self.nsfw.as_deref().map(NsfwMode::from_query_param)
+  }
+  fn post_kind(&self) -> Option<PostKind> {
+    self.post_kind.as_deref().and_then(PostKind::from_query_param)
+  }
+  /// Splits a comma-separated query param into a lowercased, trimmed list,
+  /// used for the community and keyword include/exclude filters.
+  fn name_list(value: &Option<String>) -> Vec<String> {
+    value
+      .as_deref()
+      .map(|v| {
+        v.split(',')
+          .map(|s| s.trim().to_lowercase())
+          .filter(|s| !s.is_empty())
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+}
+
+/// The output formats a feed endpoint can serve. RSS remains the default so
+/// that existing subscriptions don't change behavior.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FeedFormat {
+  Rss,
+  Atom,
+  Json,
+}
+
+impl FeedFormat {
+  fn from_query_param(format: &str) -> Self {
+    match format {
+      "atom" => FeedFormat::Atom,
+      "json" => FeedFormat::Json,
+      _ => FeedFormat::Rss,
+    }
+  }
+
+  fn from_accept_header(accept: &str) -> Self {
+    if accept.contains("atom") {
+      FeedFormat::Atom
+    } else if accept.contains("json") {
+      FeedFormat::Json
+    } else {
+      FeedFormat::Rss
+    }
+  }
+
+  fn content_type(self) -> &'static str {
+    match self {
+      FeedFormat::Rss => "application/rss+xml",
+      FeedFormat::Atom => "application/atom+xml",
+      FeedFormat::Json => "application/feed+json",
+    }
+  }
+}
+
+/// The `nsfw=hide|only|show` feed filter. Only takes effect when the caller
+/// passes it explicitly; see [`Params::nsfw_mode`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NsfwMode {
+  Hide,
+  Only,
+  Show,
+}
+
+impl NsfwMode {
+  fn from_query_param(value: &str) -> Self {
+    match value {
+      "only" => NsfwMode::Only,
+      "show" => NsfwMode::Show,
+      _ => NsfwMode::Hide,
+    }
+  }
+  /// The `PostQuery`/`PersonContentCombinedQuery` `show_nsfw` value this mode
+  /// maps onto. `Only` still needs a client-side pass afterward, since
+  /// there's no "nsfw only" field to push it into.
+  fn show_nsfw(self) -> bool {
+    match self {
+      NsfwMode::Hide => false,
+      NsfwMode::Only | NsfwMode::Show => true,
+    }
+  }
+}
+
+/// The `type=link|text|image` feed filter, derived from a post's URL and
+/// mime type the same way `create_post_items` decides how to render it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PostKind {
+  Link,
+  Text,
+  Image,
+}
+
+impl PostKind {
+  fn from_query_param(value: &str) -> Option<Self> {
+    match value {
+      "link" => Some(PostKind::Link),
+      "text" => Some(PostKind::Text),
+      "image" => Some(PostKind::Image),
+      _ => None,
+    }
+  }
+
+  fn of(post: &PostView) -> Self {
+    let is_image = post
+      .post
+      .url_content_type
+      .as_deref()
+      .is_some_and(|mime| mime.starts_with("image/"));
+    match (&post.post.url, is_image) {
+      (Some(_), true) => PostKind::Image,
+      (Some(_), false) => PostKind::Link,
+      (None, _) => PostKind::Text,
+    }
+  }
+}
+
+/// The feed content filters that don't map onto an existing query field —
+/// `nsfw=only`, `include_communities`/`exclude_communities`,
+/// `keywords`/`exclude_keywords`, and `type` — applied to an already
+/// over-fetched page (see [`Params::fetch_limit`]). `nsfw=hide|show` and
+/// `min_score` are pushed into the query itself instead; see the
+/// `show_nsfw`/`min_score` fields set at each call site.
+struct ContentFilters {
+  nsfw_only: bool,
+  include_communities: Vec<String>,
+  exclude_communities: Vec<String>,
+  keywords: Vec<String>,
+  exclude_keywords: Vec<String>,
+  post_kind: Option<PostKind>,
+  limit: usize,
+}
+
+impl ContentFilters {
+  fn new(info: &Params) -> Self {
+    ContentFilters {
+      nsfw_only: info.nsfw_mode() == Some(NsfwMode::Only),
+      include_communities: Params::name_list(&info.include_communities),
+      exclude_communities: Params::name_list(&info.exclude_communities),
+      keywords: Params::name_list(&info.keywords),
+      exclude_keywords: Params::name_list(&info.exclude_keywords),
+      post_kind: info.post_kind(),
+      limit: usize::try_from(info.get_limit()).unwrap_or(usize::MAX),
+    }
+  }
+
+  fn matches(&self, post: &PostView) -> bool {
+    if self.nsfw_only && !post.post.nsfw {
+      return false;
+    }
+    let community = post.community.name.to_lowercase();
+    if !self.include_communities.is_empty() && !self.include_communities.contains(&community) {
+      return false;
+    }
+    if self.exclude_communities.contains(&community) {
+      return false;
+    }
+    let haystack = format!(
+      "{} {}",
+      post.post.name.to_lowercase(),
+      post.post.body.as_deref().unwrap_or("").to_lowercase()
+    );
+    if !self.keywords.is_empty() && !self.keywords.iter().any(|k| haystack.contains(k)) {
+      return false;
+    }
+    if self.exclude_keywords.iter().any(|k| haystack.contains(k)) {
+      return false;
+    }
+    self
+      .post_kind
+      .map_or(true, |kind| PostKind::of(post) == kind)
+  }
+}
+
+/// Applies [`ContentFilters`] to an already over-fetched page of posts, then
+/// truncates back down to the requested page size.
+fn apply_content_filters(posts: Vec<PostView>, info: &Params) -> Vec<PostView> {
+  let filters = ContentFilters::new(info);
+  let mut posts: Vec<PostView> = posts.into_iter().filter(|p| filters.matches(p)).collect();
+  posts.truncate(filters.limit);
+  posts
+}
+
+/// The RFC 5005 "next"/"previous" page URLs for a feed, per [`build_page_urls`].
+struct PageUrls {
+  next: Option<String>,
+  prev: Option<String>,
+}
+
+/// Builds the RFC 5005 "next" and "previous" page URLs out of the current
+/// request path, the other feed params the caller passed, and the keyset
+/// cursors of the first and last row of this page.
+fn build_page_urls(
+  req: &HttpRequest,
+  info: &Params,
+  page_len: usize,
+  first_cursor: Option<PaginationCursor>,
+  last_cursor: Option<PaginationCursor>,
+) -> PageUrls {
+  // Only advertise `next` when a full page came back. A short page means the
+  // query ran out of rows, or that client-side content filters kept fewer
+  // than a page of the over-fetched rows; in both cases no `next` link is
+  // given, even though in the second case more matching rows may exist.
+  let next = (page_len as i64 == info.get_limit())
+    .then_some(last_cursor)
+    .flatten()
+    .map(|cursor| build_page_url(req, info, &cursor, false));
+  // Only advertise `prev` once we're already paging, i.e. this request
+  // itself arrived with a cursor. The very first page has nothing before it.
+  let prev = info
+    .page_cursor
+    .is_some()
+    .then_some(first_cursor)
+    .flatten()
+    .map(|cursor| build_page_url(req, info, &cursor, true));
+  PageUrls { next, prev }
+}
+
+fn build_page_url(
+  req: &HttpRequest,
+  info: &Params,
+  cursor: &PaginationCursor,
+  page_back: bool,
+) -> String {
+  let mut query = vec![format!(
+    "page_cursor={}",
+    utf8_percent_encode(&cursor.0, NON_ALPHANUMERIC)
+  )];
+  if page_back {
+    query.push("page_back=true".to_string());
+  }
+  if let Some(sort) = &info.sort {
+    query.push(format!("sort={sort}"));
+  }
+  if let Some(limit) = info.limit {
+    query.push(format!("limit={limit}"));
+  }
+  if let Some(format) = &info.format {
+    query.push(format!("format={format}"));
+  }
+  // Every content-filter param has to be carried along too, or a reader
+  // walking `next`/`prev` silently falls back to an unfiltered feed on page
+  // two: see `Params::has_unindexed_filters` and the `min_score`/`nsfw`
+  // fields pushed into the query in `get_feed_community` and friends.
+  if let Some(nsfw) = &info.nsfw {
+    query.push(format!("nsfw={nsfw}"));
+  }
+  if let Some(min_score) = info.min_score {
+    query.push(format!("min_score={min_score}"));
+  }
+  if let Some(include_communities) = &info.include_communities {
+    query.push(format!(
+      "include_communities={}",
+      utf8_percent_encode(include_communities, NON_ALPHANUMERIC)
+    ));
+  }
+  if let Some(exclude_communities) = &info.exclude_communities {
+    query.push(format!(
+      "exclude_communities={}",
+      utf8_percent_encode(exclude_communities, NON_ALPHANUMERIC)
+    ));
+  }
+  if let Some(keywords) = &info.keywords {
+    query.push(format!(
+      "keywords={}",
+      utf8_percent_encode(keywords, NON_ALPHANUMERIC)
+    ));
+  }
+  if let Some(exclude_keywords) = &info.exclude_keywords {
+    query.push(format!(
+      "exclude_keywords={}",
+      utf8_percent_encode(exclude_keywords, NON_ALPHANUMERIC)
+    ));
+  }
+  if let Some(post_kind) = &info.post_kind {
+    query.push(format!(
+      "type={}",
+      utf8_percent_encode(post_kind, NON_ALPHANUMERIC)
+    ));
+  }
+  format!("{}?{}", req.path(), query.join("&"))
+}
+
+/// This feed's canonical `self` URL and the WebSub `hub` URL it advertises,
+/// shared by every feed that supports push delivery.
+fn websub_links(req: &HttpRequest, context: &LemmyContext) -> (Option<String>, Option<String>) {
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+  let self_url = format!("{protocol_and_hostname}{}", req.path());
+  (Some(self_url), Some(websub::hub_url(&protocol_and_hostname)))
+}
+
+/// Resolves a feed token from the `/feeds/front/{token}.xml` and
+/// `/feeds/inbox/{token}.xml` path segment to the local user it was minted
+/// for. Unlike a session JWT, a feed token is scoped to a single feed,
+/// individually revocable, and never grants write access, so it's safe to
+/// have sitting in browser history or a feed reader's database.
+async fn local_user_view_from_feed_token(
+  token: &str,
+  scope: FeedTokenScope,
+  context: &LemmyContext,
+) -> LemmyResult<LocalUserView> {
+  let local_user_id = FeedToken::read_and_validate(&mut context.pool(), token, scope).await?;
+  LocalUserView::read(&mut context.pool(), local_user_id).await
 }
 
 enum RequestType {
@@ -60,10 +456,28 @@ enum RequestType {
   Inbox,
 }
 
+/// Who/what a `/feeds/{type}/{name}/comments.xml` feed lists comments for.
+enum CommentRequestType {
+  Community,
+  User,
+  Post,
+}
+
+// `front` and `inbox` feeds take a feed token (minted, listed and revoked
+// through `CreateFeedToken`/`ListFeedTokensResponse`/`DeleteFeedToken` in the
+// account settings API) rather than a session JWT, so the path segment never
+// contains a credential that grants write access.
 pub fn config(cfg: &mut web::ServiceConfig) {
+  // Registered ahead of the `/feeds` scope: a scope claims every path under
+  // its prefix, so `/feeds/hub` would never be reached if added after it.
+  websub::config(cfg);
   cfg.service(
     web::scope("/feeds")
       .route("/{type}/{name}.xml", web::get().to(get_feed))
+      .route(
+        "/{type}/{name}/comments.xml",
+        web::get().to(get_feed_comments),
+      )
       .route("/all.xml", web::get().to(get_all_feed).wrap(cache_1hour()))
       .route(
         "/local.xml",
@@ -82,78 +496,421 @@ static RSS_NAMESPACE: LazyLock<BTreeMap<String, String>> = LazyLock::new(|| {
     "media".to_string(),
     "http://search.yahoo.com/mrss/".to_string(),
   );
+  h.insert("atom".to_string(), "http://www.w3.org/2005/Atom".to_string());
   h
 });
 
+/// An output-format-neutral feed entry. Built once from the DB views and then
+/// rendered into RSS, Atom, or JSON Feed depending on what the client asked for.
+struct FeedEntry {
+  /// Globally-unique, stable identifier (the permalink).
+  id: String,
+  title: String,
+  link: String,
+  published: DateTime<Utc>,
+  author_name: String,
+  author_link: String,
+  content_html: String,
+  category: Option<Category>,
+  enclosure: Option<(String, String)>,
+  thumbnail_url: Option<String>,
+}
+
+/// A feed, independent of output format, ready to be rendered as RSS, Atom or
+/// JSON Feed.
+struct FeedChannel {
+  title: String,
+  link: String,
+  description: Option<String>,
+  entries: Vec<FeedEntry>,
+  /// URL of the next page, per RFC 5005, if there are more items to walk.
+  next_url: Option<String>,
+  /// URL of the previous page, per RFC 5005, if this isn't the first page.
+  prev_url: Option<String>,
+  /// This feed's own canonical URL, advertised as `rel="self"` so a WebSub
+  /// subscriber knows what topic it's subscribing to.
+  self_url: Option<String>,
+  /// The WebSub hub endpoint, advertised as `rel="hub"`, if this feed
+  /// supports push delivery.
+  hub_url: Option<String>,
+}
+
+impl FeedChannel {
+  fn into_response(self, format: FeedFormat) -> HttpResponse {
+    let body = match format {
+      FeedFormat::Rss => self.into_rss(),
+      FeedFormat::Atom => self.into_atom(),
+      FeedFormat::Json => self.into_json_feed(),
+    };
+
+    HttpResponse::Ok()
+      .content_type(format.content_type())
+      .body(body)
+  }
+
+  fn into_rss(self) -> String {
+    let items = self
+      .entries
+      .into_iter()
+      .map(FeedEntry::into_rss_item)
+      .collect();
+
+    let mut channel = Channel {
+      namespaces: RSS_NAMESPACE.clone(),
+      title: self.title,
+      link: self.link,
+      items,
+      ..Default::default()
+    };
+
+    if let Some(description) = self.description {
+      channel.set_description(description);
+    }
+
+    // RFC 5005 pagination and WebSub discovery: <atom:link rel="..." href="..."/>
+    let mut atom_links = Vec::new();
+    if let Some(next_url) = &self.next_url {
+      atom_links.push(atom_link_ext("next", next_url));
+    }
+    if let Some(prev_url) = &self.prev_url {
+      atom_links.push(atom_link_ext("prev", prev_url));
+    }
+    if let Some(self_url) = &self.self_url {
+      atom_links.push(atom_link_ext("self", self_url));
+    }
+    if let Some(hub_url) = &self.hub_url {
+      atom_links.push(atom_link_ext("hub", hub_url));
+    }
+    if !atom_links.is_empty() {
+      let mut extensions = ExtensionMap::new();
+      extensions.insert(
+        "atom".to_string(),
+        BTreeMap::from([("link".to_string(), atom_links)]),
+      );
+      channel.extensions = extensions;
+    }
+
+    channel.to_string()
+  }
+
+  fn into_atom(self) -> String {
+    let entries = self
+      .entries
+      .iter()
+      .map(FeedEntry::to_atom_entry)
+      .collect::<Vec<_>>();
+
+    // Newest-first is just the default sort; don't assume it when picking the
+    // feed's `updated` timestamp.
+    let updated = self
+      .entries
+      .iter()
+      .map(|e| e.published)
+      .max()
+      .map(DateTime::<FixedOffset>::from)
+      .unwrap_or_else(|| Utc::now().into());
+
+    // `self.link` is the human-facing page (the bare hostname for `all.xml`
+    // and `local.xml`), which isn't feed-specific and isn't unique between
+    // them. `self_url` is this feed's own canonical URL, so prefer it for the
+    // globally-unique `<id>` and only fall back to `link` for feeds that
+    // don't advertise a self url (comment feeds, inbox).
+    let id = self.self_url.clone().unwrap_or_else(|| self.link.clone());
+
+    let mut links = vec![LinkBuilder::default().href(self.link.clone()).build()];
+    if let Some(next_url) = self.next_url {
+      links.push(
+        LinkBuilder::default()
+          .href(next_url)
+          .rel("next".to_string())
+          .build(),
+      );
+    }
+    if let Some(prev_url) = self.prev_url {
+      links.push(
+        LinkBuilder::default()
+          .href(prev_url)
+          .rel("prev".to_string())
+          .build(),
+      );
+    }
+    if let Some(self_url) = self.self_url {
+      links.push(
+        LinkBuilder::default()
+          .href(self_url)
+          .rel("self".to_string())
+          .build(),
+      );
+    }
+    if let Some(hub_url) = self.hub_url {
+      links.push(
+        LinkBuilder::default()
+          .href(hub_url)
+          .rel("hub".to_string())
+          .build(),
+      );
+    }
+
+    let mut feed = FeedBuilder::default()
+      .title(self.title)
+      .id(id)
+      .updated(updated)
+      .links(links)
+      .entries(entries)
+      .build();
+
+    if let Some(description) = self.description {
+      feed.set_subtitle(Some(description.into()));
+    }
+
+    feed.to_string()
+  }
+
+  fn into_json_feed(self) -> String {
+    let feed = JsonFeed {
+      version: "https://jsonfeed.org/version/1.1",
+      title: self.title,
+      home_page_url: Some(self.link),
+      feed_url: self.self_url,
+      description: self.description,
+      next_url: self.next_url,
+      hubs: self
+        .hub_url
+        .map(|url| vec![JsonFeedHub { hub_type: "WebSub", url }]),
+      items: self
+        .entries
+        .into_iter()
+        .map(FeedEntry::into_json_item)
+        .collect(),
+    };
+
+    // A handwritten struct can't fail to serialize.
+    #[allow(clippy::unwrap_used)]
+    serde_json::to_string(&feed).unwrap()
+  }
+}
+
+/// Builds an `<atom:link rel="..." href="..."/>` extension, used for RFC 5005
+/// pagination links and WebSub hub/self discovery alike.
+fn atom_link_ext(rel: &str, href: &str) -> rss::extension::Extension {
+  let mut link = ExtensionBuilder::default();
+  link.name("atom:link".to_string());
+  link.attrs(BTreeMap::from([
+    ("rel".to_string(), rel.to_string()),
+    ("href".to_string(), href.to_string()),
+  ]));
+  link.build()
+}
+
+impl FeedEntry {
+  fn into_rss_item(self) -> Item {
+    let guid = Some(Guid {
+      permalink: true,
+      value: self.id,
+    });
+    let dublin_core_ext = Some(DublinCoreExtension {
+      creators: vec![self.author_link],
+      ..DublinCoreExtension::default()
+    });
+    let enclosure = self.enclosure.map(|(url, mime_type)| {
+      let mut enclosure_bld = EnclosureBuilder::default();
+      enclosure_bld.url(url);
+      enclosure_bld.mime_type(mime_type);
+      enclosure_bld.length("0".to_string());
+      enclosure_bld.build()
+    });
+
+    let mut extensions = ExtensionMap::new();
+    // See https://www.rssboard.org/media-rss#media-content for details.
+    if let Some(url) = self.thumbnail_url {
+      let mut thumbnail_ext = ExtensionBuilder::default();
+      thumbnail_ext.name("media:content".to_string());
+      thumbnail_ext.attrs(BTreeMap::from([
+        ("url".to_string(), url),
+        ("medium".to_string(), "image".to_string()),
+      ]));
+
+      extensions.insert(
+        "media".to_string(),
+        BTreeMap::from([("content".to_string(), vec![thumbnail_ext.build()])]),
+      );
+    }
+
+    Item {
+      title: Some(self.title),
+      pub_date: Some(self.published.to_rfc2822()),
+      comments: Some(self.link.clone()),
+      guid,
+      description: Some(self.content_html),
+      dublin_core_ext,
+      link: Some(self.link),
+      enclosure,
+      extensions,
+      categories: self.category.into_iter().collect(),
+      ..Default::default()
+    }
+  }
+
+  fn to_atom_entry(&self) -> AtomEntry {
+    let content = ContentBuilder::default()
+      .content_type(Some("html".to_string()))
+      .value(Some(self.content_html.clone()))
+      .build();
+    let author = PersonBuilder::default()
+      .name(self.author_name.clone())
+      .uri(Some(self.author_link.clone()))
+      .build();
+    let published: DateTime<FixedOffset> = self.published.into();
+
+    EntryBuilder::default()
+      .id(self.id.clone())
+      .title(self.title.clone())
+      .updated(published)
+      .published(Some(published))
+      .authors(vec![author])
+      .links(vec![LinkBuilder::default().href(self.link.clone()).build()])
+      .content(Some(content))
+      .build()
+  }
+
+  fn into_json_item(self) -> JsonFeedItem {
+    JsonFeedItem {
+      id: self.id,
+      url: Some(self.link),
+      title: Some(self.title),
+      content_html: Some(self.content_html),
+      date_published: Some(self.published.to_rfc3339()),
+      author: Some(JsonFeedAuthor {
+        name: self.author_name,
+        url: Some(self.author_link),
+      }),
+    }
+  }
+}
+
+/// A minimal JSON Feed 1.1 document. See https://jsonfeed.org/version/1.1
+#[derive(Serialize)]
+struct JsonFeed {
+  version: &'static str,
+  title: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  home_page_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  feed_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  next_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  hubs: Option<Vec<JsonFeedHub>>,
+  items: Vec<JsonFeedItem>,
+}
+
+/// A WebSub hub entry in a JSON Feed's `hubs` array. See
+/// <https://jsonfeed.org/version/1.1#hubs> and <https://www.w3.org/TR/websub/>.
+#[derive(Serialize)]
+struct JsonFeedHub {
+  #[serde(rename = "type")]
+  hub_type: &'static str,
+  url: String,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem {
+  id: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  title: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  content_html: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  date_published: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  author: Option<JsonFeedAuthor>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedAuthor {
+  name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  url: Option<String>,
+}
+
 async fn get_all_feed(
+  req: HttpRequest,
   info: web::Query<Params>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, Error> {
+  let format = info.feed_format(&req);
+  let sort_type = info.sort_type()?;
   Ok(
-    get_feed_data(
-      &context,
-      ListingType::All,
-      info.sort_type()?,
-      info.get_limit(),
-    )
-    .await?,
+    get_feed_data(&context, &req, &info, ListingType::All, sort_type)
+      .await?
+      .into_response(format),
   )
 }
 
 async fn get_local_feed(
+  req: HttpRequest,
   info: web::Query<Params>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, Error> {
+  let format = info.feed_format(&req);
+  let sort_type = info.sort_type()?;
   Ok(
-    get_feed_data(
-      &context,
-      ListingType::Local,
-      info.sort_type()?,
-      info.get_limit(),
-    )
-    .await?,
+    get_feed_data(&context, &req, &info, ListingType::Local, sort_type)
+      .await?
+      .into_response(format),
   )
 }
 
 async fn get_feed_data(
   context: &LemmyContext,
+  req: &HttpRequest,
+  info: &Params,
   listing_type: ListingType,
   sort_type: PostSortType,
-  limit: i64,
-) -> LemmyResult<HttpResponse> {
+) -> LemmyResult<FeedChannel> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
 
   check_private_instance(&None, &site_view.local_site)?;
 
+  let cursor_data = info.cursor_data::<PostView>(&mut context.pool()).await?;
   let posts = PostQuery {
     listing_type: (Some(listing_type)),
     sort: (Some(sort_type)),
-    limit: (Some(limit)),
+    limit: (Some(info.fetch_limit())),
+    show_nsfw: info.nsfw_mode().map(NsfwMode::show_nsfw),
+    min_score: info.min_score,
+    cursor_data,
+    page_back: info.page_back,
     ..Default::default()
   }
   .list(&site_view.site, &mut context.pool())
   .await?;
 
-  let items = create_post_items(posts, context.settings())?;
+  let posts = apply_content_filters(posts, info);
+  let PageUrls { next: next_url, prev: prev_url } = build_page_urls(
+    req,
+    info,
+    posts.len(),
+    posts.first().map(PaginationCursorBuilder::to_cursor),
+    posts.last().map(PaginationCursorBuilder::to_cursor),
+  );
+  let (self_url, hub_url) = websub_links(req, context);
+  let entries = create_post_items(posts, context.settings())?;
 
-  let mut channel = Channel {
-    namespaces: RSS_NAMESPACE.clone(),
+  Ok(FeedChannel {
     title: format!("{} - {}", site_view.site.name, listing_type),
     link: context.settings().get_protocol_and_hostname(),
-    items,
-    ..Default::default()
-  };
-
-  if let Some(site_desc) = site_view.site.description {
-    channel.set_description(&site_desc);
-  }
-
-  let rss = channel.to_string();
-  Ok(
-    HttpResponse::Ok()
-      .content_type("application/rss+xml")
-      .body(rss),
-  )
+    description: site_view.site.description,
+    entries,
+    next_url,
+    prev_url,
+    self_url,
+    hub_url,
+  })
 }
 
 async fn get_feed(
@@ -163,6 +920,7 @@ async fn get_feed(
 ) -> Result<HttpResponse, Error> {
   let req_type: String = req.match_info().get("type").unwrap_or("none").parse()?;
   let param: String = req.match_info().get("name").unwrap_or("none").parse()?;
+  let format = info.feed_format(&req);
 
   let request_type = match req_type.as_str() {
     "u" => RequestType::User,
@@ -172,32 +930,52 @@ async fn get_feed(
     _ => return Err(ErrorBadRequest(LemmyError::from(anyhow!("wrong_type")))),
   };
 
-  let builder = match request_type {
-    RequestType::User => get_feed_user(&context, &info.get_limit(), &param).await,
+  let channel = match request_type {
+    RequestType::User => get_feed_user(&context, &req, &info, &param).await,
     RequestType::Community => {
-      get_feed_community(&context, &info.sort_type()?, &info.get_limit(), &param).await
-    }
-    RequestType::Front => {
-      get_feed_front(&context, &info.sort_type()?, &info.get_limit(), &param).await
+      get_feed_community(&context, &req, &info, info.sort_type()?, &param).await
     }
+    RequestType::Front => get_feed_front(&context, &req, &info, info.sort_type()?, &param).await,
     RequestType::Inbox => get_feed_inbox(&context, &param).await,
   }
   .map_err(ErrorBadRequest)?;
 
-  let rss = builder.to_string();
+  Ok(channel.into_response(format))
+}
 
-  Ok(
-    HttpResponse::Ok()
-      .content_type("application/rss+xml")
-      .body(rss),
-  )
+async fn get_feed_comments(
+  req: HttpRequest,
+  info: web::Query<Params>,
+  context: web::Data<LemmyContext>,
+) -> Result<HttpResponse, Error> {
+  let req_type: String = req.match_info().get("type").unwrap_or("none").parse()?;
+  let param: String = req.match_info().get("name").unwrap_or("none").parse()?;
+  let format = info.feed_format(&req);
+
+  let request_type = match req_type.as_str() {
+    "u" => CommentRequestType::User,
+    "c" => CommentRequestType::Community,
+    "post" => CommentRequestType::Post,
+    _ => return Err(ErrorBadRequest(LemmyError::from(anyhow!("wrong_type")))),
+  };
+
+  let channel = match request_type {
+    CommentRequestType::User => get_feed_user_comments(&context, &info.get_limit(), &param).await,
+    CommentRequestType::Community => {
+      get_feed_community_comments(&context, &info.get_limit(), &param).await
+    }
+    CommentRequestType::Post => get_feed_post_comments(&context, &info.get_limit(), &param).await,
+  }
+  .map_err(ErrorBadRequest)?;
+
+  Ok(channel.into_response(format))
 }
 
-async fn get_feed_user(
+async fn get_feed_user_comments(
   context: &LemmyContext,
   limit: &i64,
   user_name: &str,
-) -> LemmyResult<Channel> {
+) -> LemmyResult<FeedChannel> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
   let person = Person::read_from_name(&mut context.pool(), user_name, false)
     .await?
@@ -207,7 +985,7 @@ async fn get_feed_user(
 
   let content = PersonContentCombinedQuery {
     creator_id: person.id,
-    type_: Some(PersonContentType::Posts),
+    type_: Some(PersonContentType::Comments),
     cursor_data: None,
     page_back: None,
     limit: (Some(*limit)),
@@ -215,31 +993,31 @@ async fn get_feed_user(
   .list(&mut context.pool(), &None, site_view.site.instance_id)
   .await?;
 
-  let posts = content
+  let comments = content
     .iter()
-    // Filter map to collect posts
-    .filter_map(|f| f.to_post_view())
+    .filter_map(|f| f.to_comment_view())
     .cloned()
-    .collect::<Vec<PostView>>();
+    .collect::<Vec<CommentView>>();
 
-  let items = create_post_items(posts, context.settings())?;
-  let channel = Channel {
-    namespaces: RSS_NAMESPACE.clone(),
-    title: format!("{} - {}", site_view.site.name, person.name),
-    link: person.ap_id.to_string(),
-    items,
-    ..Default::default()
-  };
+  let entries = create_comment_items(comments, context.settings())?;
 
-  Ok(channel)
+  Ok(FeedChannel {
+    title: format!("{} - {} - comments", site_view.site.name, person.name),
+    link: person.ap_id.to_string(),
+    description: None,
+    entries,
+    next_url: None,
+    prev_url: None,
+    self_url: None,
+    hub_url: None,
+  })
 }
 
-async fn get_feed_community(
+async fn get_feed_community_comments(
   context: &LemmyContext,
-  sort_type: &PostSortType,
   limit: &i64,
   community_name: &str,
-) -> LemmyResult<Channel> {
+) -> LemmyResult<FeedChannel> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
   let community = Community::read_from_name(&mut context.pool(), community_name, false)
     .await?
@@ -250,8 +1028,7 @@ async fn get_feed_community(
 
   check_private_instance(&None, &site_view.local_site)?;
 
-  let posts = PostQuery {
-    sort: (Some(*sort_type)),
+  let comments = CommentQuery {
     community_id: (Some(community.id)),
     limit: (Some(*limit)),
     ..Default::default()
@@ -259,65 +1036,231 @@ async fn get_feed_community(
   .list(&site_view.site, &mut context.pool())
   .await?;
 
-  let items = create_post_items(posts, context.settings())?;
+  let entries = create_comment_items(comments, context.settings())?;
 
-  let mut channel = Channel {
-    namespaces: RSS_NAMESPACE.clone(),
-    title: format!("{} - {}", site_view.site.name, community.name),
+  Ok(FeedChannel {
+    title: format!("{} - {} - comments", site_view.site.name, community.name),
     link: community.ap_id.to_string(),
-    items,
+    description: community.description.map(markdown_to_html),
+    entries,
+    next_url: None,
+    prev_url: None,
+    self_url: None,
+    hub_url: None,
+  })
+}
+
+async fn get_feed_post_comments(
+  context: &LemmyContext,
+  limit: &i64,
+  post_id: &str,
+) -> LemmyResult<FeedChannel> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let post_id = PostId(post_id.parse::<i32>().map_err(|_| LemmyErrorType::NotFound)?);
+
+  check_private_instance(&None, &site_view.local_site)?;
+
+  let comments = CommentQuery {
+    post_id: (Some(post_id)),
+    limit: (Some(*limit)),
     ..Default::default()
-  };
+  }
+  .list(&site_view.site, &mut context.pool())
+  .await?;
+
+  let entries = create_comment_items(comments, context.settings())?;
+
+  Ok(FeedChannel {
+    title: format!("{} - comments on post {}", site_view.site.name, post_id.0),
+    link: context.settings().get_protocol_and_hostname(),
+    description: None,
+    entries,
+    next_url: None,
+    prev_url: None,
+    self_url: None,
+    hub_url: None,
+  })
+}
+
+async fn get_feed_user(
+  context: &LemmyContext,
+  req: &HttpRequest,
+  info: &Params,
+  user_name: &str,
+) -> LemmyResult<FeedChannel> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let person = Person::read_from_name(&mut context.pool(), user_name, false)
+    .await?
+    .ok_or(LemmyErrorType::NotFound)?;
+
+  check_private_instance(&None, &site_view.local_site)?;
+
+  let cursor_data = info
+    .cursor_data::<PersonContentCombinedView>(&mut context.pool())
+    .await?;
+  let content = PersonContentCombinedQuery {
+    creator_id: person.id,
+    type_: Some(PersonContentType::Posts),
+    show_nsfw: info.nsfw_mode().map(NsfwMode::show_nsfw),
+    min_score: info.min_score,
+    cursor_data,
+    page_back: info.page_back,
+    limit: (Some(info.fetch_limit())),
+  }
+  .list(&mut context.pool(), &None, site_view.site.instance_id)
+  .await?;
+
+  // Filter on the (cursor, post) pairs rather than on the posts alone, so the
+  // `next`/`prev` cursors below point at the actual row a filtered, truncated
+  // page ends/starts on, not a row from before over-fetching and filtering.
+  let filters = ContentFilters::new(info);
+  let mut matched: Vec<(PaginationCursor, PostView)> = content
+    .iter()
+    .filter_map(|c| c.to_post_view().map(|post| (c, post)))
+    .filter(|(_, post)| filters.matches(post))
+    .map(|(c, post)| (PaginationCursorBuilder::to_cursor(c), post.clone()))
+    .collect();
+  matched.truncate(filters.limit);
+
+  // `build_page_urls` re-serializes every filter param from `info` (nsfw,
+  // min_score, community/keyword include-exclude, type) onto the next/prev
+  // link, so walking this filtered, over-fetched page doesn't silently drop
+  // back to an unfiltered feed on page two.
+  let PageUrls { next: next_url, prev: prev_url } = build_page_urls(
+    req,
+    info,
+    matched.len(),
+    matched.first().map(|(cursor, _)| cursor.clone()),
+    matched.last().map(|(cursor, _)| cursor.clone()),
+  );
+  let posts: Vec<PostView> = matched.into_iter().map(|(_, post)| post).collect();
+  let (self_url, hub_url) = websub_links(req, context);
+
+  let entries = create_post_items(posts, context.settings())?;
+
+  Ok(FeedChannel {
+    title: format!("{} - {}", site_view.site.name, person.name),
+    link: person.ap_id.to_string(),
+    description: None,
+    entries,
+    next_url,
+    prev_url,
+    self_url,
+    hub_url,
+  })
+}
 
-  if let Some(community_desc) = community.description {
-    channel.set_description(markdown_to_html(&community_desc));
+async fn get_feed_community(
+  context: &LemmyContext,
+  req: &HttpRequest,
+  info: &Params,
+  sort_type: PostSortType,
+  community_name: &str,
+) -> LemmyResult<FeedChannel> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let community = Community::read_from_name(&mut context.pool(), community_name, false)
+    .await?
+    .ok_or(LemmyErrorType::NotFound)?;
+  if !community.visibility.can_view_without_login() {
+    return Err(LemmyErrorType::NotFound.into());
   }
 
-  Ok(channel)
+  check_private_instance(&None, &site_view.local_site)?;
+
+  let cursor_data = info.cursor_data::<PostView>(&mut context.pool()).await?;
+  let posts = PostQuery {
+    sort: (Some(sort_type)),
+    community_id: (Some(community.id)),
+    limit: (Some(info.fetch_limit())),
+    show_nsfw: info.nsfw_mode().map(NsfwMode::show_nsfw),
+    min_score: info.min_score,
+    cursor_data,
+    page_back: info.page_back,
+    ..Default::default()
+  }
+  .list(&site_view.site, &mut context.pool())
+  .await?;
+
+  let posts = apply_content_filters(posts, info);
+  let PageUrls { next: next_url, prev: prev_url } = build_page_urls(
+    req,
+    info,
+    posts.len(),
+    posts.first().map(PaginationCursorBuilder::to_cursor),
+    posts.last().map(PaginationCursorBuilder::to_cursor),
+  );
+  let (self_url, hub_url) = websub_links(req, context);
+  let entries = create_post_items(posts, context.settings())?;
+
+  Ok(FeedChannel {
+    title: format!("{} - {}", site_view.site.name, community.name),
+    link: community.ap_id.to_string(),
+    description: community.description.map(markdown_to_html),
+    entries,
+    next_url,
+    prev_url,
+    self_url,
+    hub_url,
+  })
 }
 
 async fn get_feed_front(
   context: &LemmyContext,
-  sort_type: &PostSortType,
-  limit: &i64,
-  jwt: &str,
-) -> LemmyResult<Channel> {
+  req: &HttpRequest,
+  info: &Params,
+  sort_type: PostSortType,
+  feed_token: &str,
+) -> LemmyResult<FeedChannel> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
-  let local_user = local_user_view_from_jwt(jwt, context).await?;
+  let local_user =
+    local_user_view_from_feed_token(feed_token, FeedTokenScope::Subscribed, context).await?;
 
   check_private_instance(&Some(local_user.clone()), &site_view.local_site)?;
 
+  let cursor_data = info.cursor_data::<PostView>(&mut context.pool()).await?;
   let posts = PostQuery {
     listing_type: (Some(ListingType::Subscribed)),
     local_user: (Some(&local_user.local_user)),
-    sort: (Some(*sort_type)),
-    limit: (Some(*limit)),
+    sort: (Some(sort_type)),
+    limit: (Some(info.fetch_limit())),
+    show_nsfw: info.nsfw_mode().map(NsfwMode::show_nsfw),
+    min_score: info.min_score,
+    cursor_data,
+    page_back: info.page_back,
     ..Default::default()
   }
   .list(&site_view.site, &mut context.pool())
   .await?;
 
+  let posts = apply_content_filters(posts, info);
+  let PageUrls { next: next_url, prev: prev_url } = build_page_urls(
+    req,
+    info,
+    posts.len(),
+    posts.first().map(PaginationCursorBuilder::to_cursor),
+    posts.last().map(PaginationCursorBuilder::to_cursor),
+  );
   let protocol_and_hostname = context.settings().get_protocol_and_hostname();
-  let items = create_post_items(posts, context.settings())?;
-  let mut channel = Channel {
-    namespaces: RSS_NAMESPACE.clone(),
+  let (self_url, hub_url) = websub_links(req, context);
+  let entries = create_post_items(posts, context.settings())?;
+
+  Ok(FeedChannel {
     title: format!("{} - Subscribed", site_view.site.name),
     link: protocol_and_hostname,
-    items,
-    ..Default::default()
-  };
-
-  if let Some(site_desc) = site_view.site.description {
-    channel.set_description(markdown_to_html(&site_desc));
-  }
-
-  Ok(channel)
+    description: site_view.site.description.map(markdown_to_html),
+    entries,
+    next_url,
+    prev_url,
+    self_url,
+    hub_url,
+  })
 }
 
-async fn get_feed_inbox(context: &LemmyContext, jwt: &str) -> LemmyResult<Channel> {
+async fn get_feed_inbox(context: &LemmyContext, feed_token: &str) -> LemmyResult<FeedChannel> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
   let local_instance_id = site_view.site.instance_id;
-  let local_user = local_user_view_from_jwt(jwt, context).await?;
+  let local_user =
+    local_user_view_from_feed_token(feed_token, FeedTokenScope::Inbox, context).await?;
   let my_person_id = local_user.person.id;
   let show_bot_accounts = Some(local_user.local_user.show_bot_accounts);
 
@@ -331,34 +1274,31 @@ async fn get_feed_inbox(context: &LemmyContext, jwt: &str) -> LemmyResult<Channe
   .await?;
 
   let protocol_and_hostname = context.settings().get_protocol_and_hostname();
-  let items = create_reply_and_mention_items(inbox, &protocol_and_hostname, context)?;
+  let entries = create_reply_and_mention_items(inbox, &protocol_and_hostname, context)?;
 
-  let mut channel = Channel {
-    namespaces: RSS_NAMESPACE.clone(),
+  Ok(FeedChannel {
     title: format!("{} - Inbox", site_view.site.name),
     link: format!("{protocol_and_hostname}/inbox"),
-    items,
-    ..Default::default()
-  };
-
-  if let Some(site_desc) = site_view.site.description {
-    channel.set_description(&site_desc);
-  }
-
-  Ok(channel)
+    description: site_view.site.description,
+    entries,
+    next_url: None,
+    prev_url: None,
+    self_url: None,
+    hub_url: None,
+  })
 }
 
 fn create_reply_and_mention_items(
   inbox: Vec<InboxCombinedView>,
   protocol_and_hostname: &str,
   context: &LemmyContext,
-) -> LemmyResult<Vec<Item>> {
-  let reply_items: Vec<Item> = inbox
+) -> LemmyResult<Vec<FeedEntry>> {
+  inbox
     .iter()
     .map(|r| match r {
       InboxCombinedView::CommentReply(v) => {
         let reply_url = v.comment.local_url(context.settings())?;
-        build_item(
+        build_entry(
           &v.creator.name,
           &v.comment.published,
           reply_url.as_str(),
@@ -368,7 +1308,7 @@ fn create_reply_and_mention_items(
       }
       InboxCombinedView::CommentMention(v) => {
         let mention_url = v.comment.local_url(context.settings())?;
-        build_item(
+        build_entry(
           &v.creator.name,
           &v.comment.published,
           mention_url.as_str(),
@@ -378,7 +1318,7 @@ fn create_reply_and_mention_items(
       }
       InboxCombinedView::PostMention(v) => {
         let mention_url = v.post.local_url(context.settings())?;
-        build_item(
+        build_entry(
           &v.creator.name,
           &v.post.published,
           mention_url.as_str(),
@@ -388,7 +1328,7 @@ fn create_reply_and_mention_items(
       }
       InboxCombinedView::PrivateMessage(v) => {
         let inbox_url = format!("{}/inbox", protocol_and_hostname);
-        build_item(
+        build_entry(
           &v.creator.name,
           &v.private_message.published,
           &inbox_url,
@@ -397,54 +1337,67 @@ fn create_reply_and_mention_items(
         )
       }
     })
-    .collect::<LemmyResult<Vec<Item>>>()?;
-
-  Ok(reply_items)
+    .collect::<LemmyResult<Vec<FeedEntry>>>()
 }
 
-fn build_item(
+fn build_entry(
   creator_name: &str,
   published: &DateTime<Utc>,
   url: &str,
   content: &str,
   protocol_and_hostname: &str,
-) -> LemmyResult<Item> {
+) -> LemmyResult<FeedEntry> {
   // TODO add images
-  let guid = Some(Guid {
-    permalink: true,
-    value: url.to_owned(),
-  });
-  let description = Some(markdown_to_html(content));
-
-  Ok(Item {
-    title: Some(format!("Reply from {creator_name}")),
-    author: Some(format!(
-      "/u/{creator_name} <a href=\"{}\">(link)</a>",
-      format_args!("{protocol_and_hostname}/u/{creator_name}")
-    )),
-    pub_date: Some(published.to_rfc2822()),
-    comments: Some(url.to_owned()),
-    link: Some(url.to_owned()),
-    guid,
-    description,
-    ..Default::default()
+  Ok(FeedEntry {
+    id: url.to_owned(),
+    title: format!("Reply from {creator_name}"),
+    link: url.to_owned(),
+    published: *published,
+    author_name: creator_name.to_owned(),
+    author_link: format!("{protocol_and_hostname}/u/{creator_name}"),
+    content_html: markdown_to_html(content),
+    category: None,
+    enclosure: None,
+    thumbnail_url: None,
   })
 }
 
-fn create_post_items(posts: Vec<PostView>, settings: &Settings) -> LemmyResult<Vec<Item>> {
-  let mut items: Vec<Item> = Vec::new();
+fn create_comment_items(
+  comments: Vec<CommentView>,
+  settings: &Settings,
+) -> LemmyResult<Vec<FeedEntry>> {
+  let mut entries: Vec<FeedEntry> = Vec::new();
+
+  for c in comments {
+    let comment_url = c.comment.local_url(settings)?;
+    let category = Some(Category {
+      name: c.community.title,
+      domain: Some(c.community.ap_id.to_string()),
+    });
+
+    entries.push(FeedEntry {
+      id: comment_url.to_string(),
+      title: format!("Comment on {}", c.post.name),
+      link: comment_url.to_string(),
+      published: c.comment.published,
+      author_name: c.creator.name,
+      author_link: c.creator.ap_id.to_string(),
+      content_html: markdown_to_html(&c.comment.content),
+      category,
+      enclosure: None,
+      thumbnail_url: None,
+    });
+  }
+
+  Ok(entries)
+}
+
+fn create_post_items(posts: Vec<PostView>, settings: &Settings) -> LemmyResult<Vec<FeedEntry>> {
+  let mut entries: Vec<FeedEntry> = Vec::new();
 
   for p in posts {
     let post_url = p.post.local_url(settings)?;
     let community_url = Community::local_url(&p.community.name, settings)?;
-    let dublin_core_ext = Some(DublinCoreExtension {
-      creators: vec![p.creator.ap_id.to_string()],
-      ..DublinCoreExtension::default()
-    });
-    let guid = Some(Guid {
-      permalink: true,
-      value: post_url.to_string(),
-    });
     let mut description = format!("submitted by <a href=\"{}\">{}</a> to <a href=\"{}\">{}</a><br>{} points | <a href=\"{}\">{} comments</a>",
     p.creator.ap_id,
     &p.creator.name,
@@ -456,7 +1409,7 @@ fn create_post_items(posts: Vec<PostView>, settings: &Settings) -> LemmyResult<V
 
     // If its a url post, add it to the description
     // and see if we can parse it as a media enclosure.
-    let enclosure_opt = p.post.url.map(|url| {
+    let enclosure = p.post.url.map(|url| {
       let mime_type = p
         .post
         .url_content_type
@@ -470,11 +1423,7 @@ fn create_post_items(posts: Vec<PostView>, settings: &Settings) -> LemmyResult<V
       };
       description.push_str(&link_html);
 
-      let mut enclosure_bld = EnclosureBuilder::default();
-      enclosure_bld.url(url.as_str().to_string());
-      enclosure_bld.mime_type(mime_type);
-      enclosure_bld.length("0".to_string());
-      enclosure_bld.build()
+      (url.to_string(), mime_type)
     });
 
     if let Some(body) = p.post.body {
@@ -482,44 +1431,30 @@ fn create_post_items(posts: Vec<PostView>, settings: &Settings) -> LemmyResult<V
       description.push_str(&html);
     }
 
-    let mut extensions = ExtensionMap::new();
-
-    // If there's a thumbnail URL, add a media:content tag to display it.
-    // See https://www.rssboard.org/media-rss#media-content for details.
-    if let Some(url) = p.post.thumbnail_url {
-      let mut thumbnail_ext = ExtensionBuilder::default();
-      thumbnail_ext.name("media:content".to_string());
-      thumbnail_ext.attrs(BTreeMap::from([
-        ("url".to_string(), url.to_string()),
-        ("medium".to_string(), "image".to_string()),
-      ]));
-
-      extensions.insert(
-        "media".to_string(),
-        BTreeMap::from([("content".to_string(), vec![thumbnail_ext.build()])]),
-      );
+    // Atom and JSON Feed readers don't understand media:content, so inline a
+    // plain <img> for them; the RSS renderer additionally adds the proper tag.
+    if let Some(url) = &p.post.thumbnail_url {
+      description.push_str(&format!("<br><img src=\"{url}\"/>"));
     }
-    let category = Category {
+
+    let category = Some(Category {
       name: p.community.title,
       domain: Some(p.community.ap_id.to_string()),
-    };
-
-    let i = Item {
-      title: Some(format!("[{}] {}", p.community.name, p.post.name)),
-      pub_date: Some(p.post.published.to_rfc2822()),
-      comments: Some(post_url.to_string()),
-      guid,
-      description: Some(description),
-      dublin_core_ext,
-      link: Some(post_url.to_string()),
-      extensions,
-      enclosure: enclosure_opt,
-      categories: vec![category],
-      ..Default::default()
-    };
+    });
 
-    items.push(i);
+    entries.push(FeedEntry {
+      id: post_url.to_string(),
+      title: format!("[{}] {}", p.community.name, p.post.name),
+      link: post_url.to_string(),
+      published: p.post.published,
+      author_name: p.creator.name,
+      author_link: p.creator.ap_id.to_string(),
+      content_html: description,
+      category,
+      enclosure,
+      thumbnail_url: p.post.thumbnail_url.map(|u| u.to_string()),
+    });
   }
 
-  Ok(items)
+  Ok(entries)
 }
@@ -0,0 +1,145 @@
+//! WebSub (PubSubHubbub) hub support for feeds: the hub-initiated
+//! verification handshake, the subscription store, and the publish-side ping
+//! that turns the feed subsystem from pull-only into near-real-time delivery.
+//! See <https://www.w3.org/TR/websub/>.
+
+use actix_web::{error::ErrorInternalServerError, web, Error, HttpResponse, Result};
+use anyhow::anyhow;
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::source::websub_subscription::{WebSubSubscription, DEFAULT_LEASE_SECONDS};
+use lemmy_utils::error::LemmyResult;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::Deserialize;
+
+const CHALLENGE_LEN: usize = 32;
+
+/// The hub endpoint advertised by every feed's `<atom:link rel="hub">`.
+pub fn hub_url(protocol_and_hostname: &str) -> String {
+  format!("{protocol_and_hostname}/feeds/hub")
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+  cfg.service(web::resource("/feeds/hub").route(web::post().to(subscribe)));
+}
+
+#[derive(Deserialize)]
+struct HubSubscribeRequest {
+  #[serde(rename = "hub.mode")]
+  mode: String,
+  #[serde(rename = "hub.topic")]
+  topic: String,
+  #[serde(rename = "hub.callback")]
+  callback: String,
+  #[serde(rename = "hub.lease_seconds")]
+  lease_seconds: Option<i64>,
+}
+
+/// Records (or removes) a subscriber's callback for a feed topic URL, per the
+/// WebSub "subscriber request" step. Per spec the hub must verify the
+/// subscriber's intent itself before a `subscribe` takes effect, so the new
+/// row starts out unconfirmed and a background verification request is sent
+/// to `callback`; only a successful verification confirms it (or, for
+/// `unsubscribe`, removes it). The 202 below just means the request was
+/// accepted for processing, not that the subscription is live yet.
+async fn subscribe(
+  form: web::Form<HubSubscribeRequest>,
+  context: web::Data<LemmyContext>,
+) -> Result<HttpResponse, Error> {
+  let topic = form.topic.clone();
+  let callback = form.callback.clone();
+  let lease_seconds = form.lease_seconds;
+
+  match form.mode.as_str() {
+    "subscribe" => {
+      WebSubSubscription::upsert(&mut context.pool(), &topic, &callback, lease_seconds)
+        .await
+        .map_err(ErrorInternalServerError)?;
+      actix_web::rt::spawn(async move {
+        let verified =
+          verify_with_subscriber(&context, &topic, &callback, "subscribe", lease_seconds).await;
+        let _ = if verified.is_ok() {
+          WebSubSubscription::mark_confirmed(&mut context.pool(), &topic, &callback).await
+        } else {
+          WebSubSubscription::remove(&mut context.pool(), &topic, &callback)
+            .await
+            .map(|_| ())
+        };
+      });
+    }
+    "unsubscribe" => {
+      actix_web::rt::spawn(async move {
+        if verify_with_subscriber(&context, &topic, &callback, "unsubscribe", lease_seconds)
+          .await
+          .is_ok()
+        {
+          let _ = WebSubSubscription::remove(&mut context.pool(), &topic, &callback).await;
+        }
+      });
+    }
+    _ => return Ok(HttpResponse::BadRequest().finish()),
+  }
+
+  Ok(HttpResponse::Accepted().finish())
+}
+
+/// The hub side of WebSub's "verification of intent" handshake: GET the
+/// subscriber's own `callback` with a freshly-generated `hub.challenge` and
+/// require it to be echoed back verbatim before trusting the request. This
+/// stops an attacker from subscribing an unrelated site's URL as `callback`
+/// and having the hub push content at it without that site's cooperation.
+async fn verify_with_subscriber(
+  context: &LemmyContext,
+  topic: &str,
+  callback: &str,
+  mode: &str,
+  lease_seconds: Option<i64>,
+) -> LemmyResult<()> {
+  let challenge: String = thread_rng()
+    .sample_iter(&Alphanumeric)
+    .take(CHALLENGE_LEN)
+    .map(char::from)
+    .collect();
+
+  let response = context
+    .client()
+    .get(callback)
+    .query(&[
+      ("hub.mode", mode),
+      ("hub.topic", topic),
+      ("hub.challenge", challenge.as_str()),
+      (
+        "hub.lease_seconds",
+        &lease_seconds.unwrap_or(DEFAULT_LEASE_SECONDS).to_string(),
+      ),
+    ])
+    .send()
+    .await?;
+
+  let body = response.text().await?;
+  if body.trim() != challenge {
+    return Err(anyhow!("websub_verification_failed").into());
+  }
+  Ok(())
+}
+
+/// Pings every confirmed subscriber of `topic` (a feed's `self_url`) that new
+/// content has landed, per the WebSub "content distribution" step. Called
+/// from `lemmy_api_crud`'s post and comment creation handlers wherever the
+/// affected community or user feed has subscribers.
+///
+/// Only the subscriber lookup is awaited here; each callback POST is handed
+/// to its own spawned task so one slow or unreachable subscriber can't
+/// serialize into (or fail) the post/comment create request that triggered
+/// the ping.
+pub async fn publish_update(context: &LemmyContext, topic: &str) -> LemmyResult<()> {
+  let subscribers = WebSubSubscription::read_confirmed_for_topic(&mut context.pool(), topic).await?;
+  for subscriber in subscribers {
+    let client = context.client().clone();
+    let topic = topic.to_owned();
+    actix_web::rt::spawn(async move {
+      // Best-effort: an unreachable subscriber shouldn't fail the triggering write.
+      let _ = client.post(&subscriber.callback).body(topic).send().await;
+    });
+  }
+  Ok(())
+}